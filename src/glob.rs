@@ -0,0 +1,354 @@
+//! Path matching for `copy_files`: an `exclude` denylist plus an
+//! optional `include` allowlist, as configured by a package's
+//! `typst.toml`; and [`PatternSet`], the gitignore-style matcher behind
+//! a package's optional `.typstignore` file.
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobMatcher};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+fn has_glob_metacharacters(s: &str) -> bool {
+    s.contains(|c| matches!(c, '*' | '?' | '[' | ']'))
+}
+
+/// A single glob paired with the literal directory prefix (if any) it's
+/// confined under, so a path outside that prefix never has to test
+/// against it.
+struct ScopedGlob {
+    base: Option<String>,
+    matcher: GlobMatcher,
+}
+
+/// Returns the literal (glob-metacharacter-free) directory prefix of
+/// `pattern`, e.g. `"build"` for `"build/**"`. `None` means the pattern
+/// has no confining prefix and so could match anywhere.
+fn literal_prefix(pattern: &str) -> Option<String> {
+    let pattern = pattern.trim_end_matches('/');
+    let prefix = match pattern.find(|c| matches!(c, '*' | '?' | '[' | ']')) {
+        Some(idx) => &pattern[..idx],
+        None => pattern,
+    };
+    let base_end = prefix.rfind('/')?;
+    let base = &prefix[..base_end];
+    (!base.is_empty()).then(|| base.to_string())
+}
+
+/// Returns true when `rel_dir` is at or below `base`, i.e. a pattern
+/// scoped to `base` could plausibly still match something under it.
+fn scope_applies(base: &str, rel_dir: &str) -> bool {
+    rel_dir == base
+        || rel_dir.starts_with(&format!("{}/", base))
+        || base.starts_with(&format!("{}/", rel_dir))
+        || rel_dir.is_empty()
+}
+
+fn build_scoped_globs(patterns: &[String]) -> Result<Vec<ScopedGlob>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            let pattern = pattern.replace('\\', "/");
+            Ok(ScopedGlob {
+                base: literal_prefix(&pattern),
+                matcher: Glob::new(&pattern)?.compile_matcher(),
+            })
+        })
+        .collect()
+}
+
+fn any_scoped_match(globs: &[ScopedGlob], rel_path: &str, rel_dir: &str) -> bool {
+    globs.iter().any(|g| {
+        g.base
+            .as_deref()
+            .is_none_or(|base| scope_applies(base, rel_dir))
+            && g.matcher.is_match(rel_path)
+    })
+}
+
+/// Matches relative paths against an `include` allowlist and an
+/// `exclude` denylist. Paths are normalized to `/` separators before
+/// matching, so behavior is identical on Windows and Unix.
+pub struct FileMatcher {
+    include: Vec<ScopedGlob>,
+    exclude: Vec<ScopedGlob>,
+    exclude_dirs: Vec<String>,
+    /// Paths that are always copied regardless of `include`/`exclude`,
+    /// the way Cargo always ships `Cargo.toml` even under a restrictive
+    /// `include` list. Holds the manifest and the resolved entrypoint.
+    forced: HashSet<String>,
+}
+
+impl FileMatcher {
+    /// Builds a matcher from a package's `include`/`exclude` patterns.
+    /// `source_dir` is used only to resolve which literal (non-glob)
+    /// exclude patterns name directories, for walk pruning. `forced_paths`
+    /// (e.g. `typst.toml` and the entrypoint) are always kept.
+    pub fn new(
+        source_dir: &Path,
+        include_patterns: &[String],
+        exclude_patterns: &[String],
+        forced_paths: &[&str],
+    ) -> Result<Self> {
+        let exclude_dirs = exclude_patterns
+            .iter()
+            .filter(|p| !has_glob_metacharacters(p))
+            .filter_map(|p| {
+                let pattern_native = p.replace('/', &std::path::MAIN_SEPARATOR.to_string());
+                let pattern_path = source_dir.join(&pattern_native);
+
+                let is_dir_pattern = if p.ends_with('/') {
+                    true
+                } else {
+                    pattern_path.is_dir()
+                };
+
+                is_dir_pattern.then(|| p.replace('\\', "/").trim_end_matches('/').to_string())
+            })
+            .collect();
+
+        let forced = forced_paths
+            .iter()
+            .map(|p| p.replace(std::path::MAIN_SEPARATOR, "/"))
+            .collect();
+
+        Ok(Self {
+            include: build_scoped_globs(include_patterns)?,
+            exclude: build_scoped_globs(exclude_patterns)?,
+            exclude_dirs,
+            forced,
+        })
+    }
+
+    /// Returns whether `rel_path` (relative to the package root, native
+    /// or `/` separators) should be copied: always true for a forced
+    /// path (manifest/entrypoint); otherwise not excluded, and - when an
+    /// `include` allowlist is configured - matching one of its patterns.
+    pub fn matches(&self, rel_path: &str) -> bool {
+        let rel_unix = rel_path.replace(std::path::MAIN_SEPARATOR, "/");
+        if self.forced.contains(&rel_unix) {
+            return true;
+        }
+
+        if self.is_excluded(rel_path) {
+            return false;
+        }
+
+        if self.include.is_empty() {
+            return true;
+        }
+
+        let rel_dir_unix = rel_unix.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+        any_scoped_match(&self.include, &rel_unix, rel_dir_unix)
+    }
+
+    /// Returns whether the directory at `rel_dir` can be pruned from the
+    /// walk entirely. Only `exclude` patterns apply here - an `include`
+    /// allowlist never prunes a directory, since files deeper inside it
+    /// may still match.
+    ///
+    /// A directory named by an exclude pattern of the form `<dir>/**`
+    /// (e.g. `vendor` for `vendor/**`) is pruned directly here, since the
+    /// compiled glob itself only matches paths at least one level under
+    /// that base, never the bare directory name.
+    pub fn is_dir_excluded(&self, rel_dir: &str) -> bool {
+        let rel_unix = rel_dir.replace(std::path::MAIN_SEPARATOR, "/");
+        let whole_dir_pattern = format!("{}/**", rel_unix);
+        if self.exclude.iter().any(|g| {
+            g.base.as_deref() == Some(rel_unix.as_str())
+                && g.matcher.glob().glob() == whole_dir_pattern
+        }) {
+            return true;
+        }
+
+        self.is_excluded(rel_dir)
+    }
+
+    fn is_excluded(&self, rel_path: &str) -> bool {
+        let rel_unix = rel_path.replace(std::path::MAIN_SEPARATOR, "/");
+        let rel_dir_unix = rel_unix.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+
+        if any_scoped_match(&self.exclude, &rel_unix, rel_dir_unix) {
+            return true;
+        }
+
+        self.exclude_dirs.iter().any(|pattern| {
+            rel_unix == *pattern || rel_unix.starts_with(&format!("{}/", pattern))
+        })
+    }
+}
+
+/// A single rule parsed from a `.typstignore` file.
+///
+/// `anchored` rules (the pattern contains a `/` before its final
+/// segment) only match against the full path relative to the package
+/// root; unanchored rules match any path segment, mirroring gitignore.
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    glob: Glob,
+    whitelist: bool,
+    anchored: bool,
+}
+
+/// An ordered set of `.typstignore`/`.gitignore`-style rules.
+///
+/// Unlike a plain globset, inclusion is decided by walking every rule
+/// in file order rather than stopping at the first match, so a later
+/// `!keep/this.typ` can re-include something an earlier `keep/**`
+/// excluded.
+#[derive(Debug, Default)]
+pub struct PatternSet {
+    rules: Vec<IgnoreRule>,
+}
+
+impl PatternSet {
+    /// Parses a patterns file, skipping empty lines and `#` comments.
+    /// Returns an empty set if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read ignore file: {}", path.display()))?;
+
+        let mut rules = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (whitelist, pattern) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+
+            // A directory entry like `build/` never has a trailing
+            // slash to match against once walked (`rel_str`/segments
+            // never do), so strip it before compiling the glob.
+            let pattern = pattern.trim_end_matches('/');
+
+            // A pattern is anchored to the package root if it contains a
+            // `/` anywhere before its last character; otherwise it can
+            // match a path segment at any depth.
+            let anchored = pattern.contains('/');
+
+            // A leading `/` (gitignore's explicit root-anchor form) is
+            // only a marker, not something `rel_path` strings from the
+            // walk ever carry - strip it before compiling the glob.
+            let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+
+            rules.push(IgnoreRule {
+                glob: Glob::new(pattern)
+                    .with_context(|| format!("Invalid pattern '{}' in {}", pattern, path.display()))?,
+                whitelist,
+                anchored,
+            });
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Walks every rule against `rel_path`, letting the final matching
+    /// rule's whitelist/exclude state win.
+    pub fn is_excluded(&self, rel_path: &str) -> bool {
+        let mut excluded = false;
+        for rule in &self.rules {
+            let matcher = rule.glob.compile_matcher();
+            let matched = if rule.anchored {
+                matcher.is_match(rel_path)
+            } else {
+                rel_path.split('/').any(|segment| matcher.is_match(segment))
+            };
+
+            if matched {
+                excluded = !rule.whitelist;
+            }
+        }
+        excluded
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forced_paths_survive_a_restrictive_include_list() {
+        let matcher = FileMatcher::new(
+            Path::new("."),
+            &["*.typ".to_string(), "LICENSE".to_string()],
+            &[],
+            &["typst.toml", "main.typ"],
+        )
+        .unwrap();
+
+        assert!(matcher.matches("typst.toml"));
+        assert!(matcher.matches("main.typ"));
+        assert!(matcher.matches("LICENSE"));
+        assert!(!matcher.matches("README.md"));
+    }
+
+    #[test]
+    fn exclude_still_drops_matching_paths() {
+        let matcher =
+            FileMatcher::new(Path::new("."), &[], &["build/**".to_string()], &[]).unwrap();
+
+        assert!(!matcher.matches("build/output.typ"));
+        assert!(matcher.matches("src/main.typ"));
+    }
+
+    #[test]
+    fn glob_exclude_prunes_its_own_base_directory() {
+        let matcher =
+            FileMatcher::new(Path::new("."), &[], &["vendor/**".to_string()], &[]).unwrap();
+
+        assert!(matcher.is_dir_excluded("vendor"));
+        assert!(!matcher.is_dir_excluded("src"));
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("typst_build_test_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn patternset_excludes_trailing_slash_directories() {
+        let path = temp_path("typstignore_dir");
+        fs::write(&path, "build/\n").unwrap();
+
+        let set = PatternSet::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(set.is_excluded("build/output.typ"));
+        assert!(!set.is_excluded("src/output.typ"));
+    }
+
+    #[test]
+    fn patternset_respects_leading_slash_root_anchor() {
+        let path = temp_path("typstignore_root_anchor");
+        fs::write(&path, "/secret.typ\n").unwrap();
+
+        let set = PatternSet::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(set.is_excluded("secret.typ"));
+        assert!(!set.is_excluded("nested/secret.typ"));
+    }
+
+    #[test]
+    fn patternset_negation_overrides_broader_exclude() {
+        let path = temp_path("typstignore_negation");
+        fs::write(&path, "keep/**\n!keep/this.typ\n").unwrap();
+
+        let set = PatternSet::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(set.is_excluded("keep/other.typ"));
+        assert!(!set.is_excluded("keep/this.typ"));
+    }
+}
@@ -1,22 +1,48 @@
 use anyhow::{Context, Ok, Result, anyhow};
-use clap::Parser;
-use globset::{Glob, GlobSetBuilder};
+use clap::{Parser, Subcommand};
 use regex::Regex;
 use serde::Deserialize;
+use std::collections::HashSet;
 use std::fs;
+use std::io::{self, Write};
 use std::{
     path::{Path, PathBuf},
     process::Command,
 };
 use walkdir::WalkDir;
 
+mod glob;
+
+use glob::{FileMatcher, PatternSet};
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
-    #[arg(value_name = "TOML_FILE")]
-    toml_file: PathBuf,
-    #[arg(long, value_name = "OUTPUT_DIR",default_value = "output", value_parser=["output","universe"])]
-    output_dir: String,
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Build a Typst package into an output directory
+    Build {
+        #[arg(value_name = "TOML_FILE")]
+        toml_file: PathBuf,
+        #[arg(long, value_name = "OUTPUT_DIR", default_value = "output", value_parser=["output","universe"])]
+        output_dir: String,
+    },
+    /// Scaffold a new Typst package
+    Init {
+        /// Package name; also used as the generated directory name.
+        /// Prompted for interactively when omitted.
+        #[arg(value_name = "NAME")]
+        name: Option<String>,
+        #[arg(long, default_value = "0.1.0")]
+        version: String,
+        /// Also scaffold a template/ directory and [template] manifest section
+        #[arg(long)]
+        template: bool,
+    },
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,6 +50,10 @@ struct PackageConfig {
     name: String,
     version: String,
     exclude: Option<Vec<String>>,
+    /// Whitelist of patterns to publish; when present, only matching
+    /// files are copied and `exclude` only narrows that set further.
+    /// `typst.toml` and the entrypoint are always copied.
+    include: Option<Vec<String>>,
     entrypoint: Option<String>,
 }
 
@@ -32,6 +62,12 @@ struct TemplateConfig {
     path: Option<String>,
     entrypoint: Option<String>,
     thumbnail: Option<String>,
+    /// Pixels-per-inch density for the rendered thumbnail; only
+    /// meaningful for raster formats. Defaults to [`DEFAULT_THUMBNAIL_PPI`].
+    thumbnail_ppi: Option<f32>,
+    /// Output format passed to `typst compile --format`; inferred from
+    /// `thumbnail`'s file extension when unset.
+    thumbnail_format: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -89,28 +125,66 @@ fn compile_template(
     Ok(())
 }
 
+/// Sensible default density for a raster thumbnail when `thumbnail_ppi`
+/// isn't set in the manifest - double the usual screen PPI so previews
+/// stay crisp.
+const DEFAULT_THUMBNAIL_PPI: f32 = 144.0;
+
+/// Typst Universe expects compact, roughly landscape/portrait preview
+/// images, not full-page renders blown up to poster size. These are
+/// soft limits: we warn rather than fail, since authors may have a
+/// good reason to exceed them.
+const THUMBNAIL_MAX_DIMENSION: u32 = 2000;
+const THUMBNAIL_MAX_ASPECT_RATIO: f32 = 3.0;
+
 fn generate_thumbnail(
     toml_dir: &Path,
     package_name: &str,
     template_path: &str,
     template_entrypoint: &str,
     thumbnail_path: &str,
+    thumbnail_ppi: Option<f32>,
+    thumbnail_format: Option<&str>,
 ) -> Result<()> {
     let template_full_path = Path::new(package_name)
         .join(template_path)
         .join(template_entrypoint);
     let thumbnail_full_path = Path::new(package_name).join(thumbnail_path);
 
+    // Infer the format from the thumbnail's extension when the
+    // manifest doesn't set one explicitly, defaulting to PNG. Normalize
+    // casing either way - `typst compile --format` expects lowercase,
+    // and a manifest author may well write `PNG`.
+    let format = thumbnail_format
+        .map(|f| f.to_lowercase())
+        .unwrap_or_else(|| {
+            Path::new(thumbnail_path)
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.to_lowercase())
+                .unwrap_or_else(|| "png".to_string())
+        });
+
+    let mut args = vec![
+        "compile".to_string(),
+        "--root".to_string(),
+        ".".to_string(),
+        "--pages".to_string(),
+        "1".to_string(),
+        "--format".to_string(),
+        format.clone(),
+    ];
+    // --ppi only makes sense for raster output; vector formats like svg
+    // or pdf reject it.
+    if format == "png" {
+        args.push("--ppi".to_string());
+        args.push(thumbnail_ppi.unwrap_or(DEFAULT_THUMBNAIL_PPI).to_string());
+    }
+    args.push(template_full_path.to_str().unwrap().to_string());
+    args.push(thumbnail_full_path.to_str().unwrap().to_string());
+
     let output = Command::new("typst")
-        .args([
-            "compile",
-            "--root",
-            ".",
-            "--pages",
-            "1",
-            template_full_path.to_str().unwrap(),
-            thumbnail_full_path.to_str().unwrap(),
-        ])
+        .args(&args)
         .current_dir(toml_dir.parent().unwrap())
         .output()
         .with_context(|| "Failed to generate thumbnail")?;
@@ -124,12 +198,62 @@ fn generate_thumbnail(
             stderr
         ));
     }
+
+    warn_if_thumbnail_oversized(&toml_dir.join(thumbnail_path));
+
     Ok(())
 }
+
+/// Reads the width/height out of a PNG's `IHDR` chunk directly, rather
+/// than pulling in an image-decoding crate just to read two integers.
+/// Returns `None` for anything that isn't a PNG, including vector
+/// thumbnail formats, which have no fixed pixel dimensions.
+fn png_dimensions(path: &Path) -> Option<(u32, u32)> {
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+    let bytes = fs::read(path).ok()?;
+    if bytes.len() < 24 || bytes[..8] != SIGNATURE || &bytes[12..16] != b"IHDR" {
+        return None;
+    }
+
+    let width = u32::from_be_bytes(bytes[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(bytes[20..24].try_into().ok()?);
+    Some((width, height))
+}
+
+/// Warns (without failing the build) when a rendered raster thumbnail
+/// exceeds Typst Universe's size or aspect-ratio expectations. Vector
+/// formats have no fixed pixel dimensions and are skipped.
+fn warn_if_thumbnail_oversized(thumbnail_path: &Path) {
+    let Some((width, height)) = png_dimensions(thumbnail_path) else {
+        return;
+    };
+
+    if width.max(height) > THUMBNAIL_MAX_DIMENSION {
+        eprintln!(
+            "Warning: thumbnail '{}' is {}x{}px, larger than the {}px Typst Universe recommends",
+            thumbnail_path.display(),
+            width,
+            height,
+            THUMBNAIL_MAX_DIMENSION
+        );
+    }
+
+    let aspect_ratio = width.max(height) as f32 / width.min(height).max(1) as f32;
+    if aspect_ratio > THUMBNAIL_MAX_ASPECT_RATIO {
+        eprintln!(
+            "Warning: thumbnail '{}' has an extreme aspect ratio ({}x{}), consider cropping it",
+            thumbnail_path.display(),
+            width,
+            height
+        );
+    }
+}
 fn copy_files(
     source_dir: &Path,
     dest_dir: &Path,
-    exclude_patterns: &[String],
+    file_matcher: &FileMatcher,
+    ignore_patterns: &PatternSet,
     package_name: &str,
     package_version: &str,
     package_entrypoint: &str,
@@ -137,59 +261,45 @@ fn copy_files(
     // Create destination directory
     std::fs::create_dir_all(dest_dir)?;
 
-    // Build glob set from exclude patterns
-    let mut glob_builder = GlobSetBuilder::new();
-    for pattern in exclude_patterns {
-        let glob = Glob::new(pattern)?;
-        glob_builder.add(glob);
-    }
-    let glob_set = glob_builder.build()?;
-
-    // Precompute directory patterns (non-glob and directories)
-    let directory_patterns: Vec<String> = exclude_patterns
-        .iter()
-        .filter(|p| !has_glob_metacharacters(p))
-        .filter_map(|p| {
-            let pattern_native = p.replace('/', &std::path::MAIN_SEPARATOR.to_string());
-            let pattern_path = source_dir.join(&pattern_native);
-
-            let is_dir_pattern = if p.ends_with('/') {
-                true
-            } else {
-                pattern_path.is_dir()
-            };
-
-            is_dir_pattern.then(|| {
-                pattern_native
-                    .trim_end_matches(std::path::MAIN_SEPARATOR)
-                    .to_string()
-            })
-        })
-        .collect();
+    // Returns true when `entry` should be excluded, i.e. not copied
+    // and - if it's a directory - never recursed into.
+    let is_excluded = |entry: &walkdir::DirEntry| -> Result<bool> {
+        if entry.depth() == 0 {
+            return Ok(false);
+        }
 
-    // Process each file
-    for entry in WalkDir::new(source_dir) {
-        let entry = entry?;
-        let src_path = entry.path();
-        let rel_path = src_path.strip_prefix(source_dir)?;
+        let rel_path = entry.path().strip_prefix(source_dir)?;
         let rel_str = rel_path.to_str().ok_or_else(|| anyhow!("Invalid path"))?;
 
-        // Check against glob patterns
-        let rel_str_unix = rel_str.replace(std::path::MAIN_SEPARATOR, "/");
-        if glob_set.is_match(&rel_str_unix) {
-            continue;
+        if entry.file_type().is_dir() {
+            let rel_str_unix = rel_str.replace(std::path::MAIN_SEPARATOR, "/");
+            return Ok(file_matcher.is_dir_excluded(rel_str)
+                || (!ignore_patterns.is_empty() && ignore_patterns.is_excluded(&rel_str_unix)));
         }
 
-        // Check against directory patterns
-        let excluded_by_dir = directory_patterns.iter().any(|pattern| {
-            rel_str == pattern
-                || rel_str.starts_with(&format!("{}{}", pattern, std::path::MAIN_SEPARATOR))
-        });
-
-        if excluded_by_dir {
-            continue;
+        if !file_matcher.matches(rel_str) {
+            return Ok(true);
         }
 
+        // Check against the .typstignore-style pattern set. It can only
+        // drop further paths: its `!` negations re-include what its own
+        // earlier lines excluded, not what `include`/`exclude` dropped.
+        let rel_str_unix = rel_str.replace(std::path::MAIN_SEPARATOR, "/");
+        Ok(!ignore_patterns.is_empty() && ignore_patterns.is_excluded(&rel_str_unix))
+    };
+
+    // Walk the tree, pruning excluded directories via `filter_entry` so
+    // we never descend into (and pay the I/O for) large excluded trees
+    // like `.git` or vendored build caches.
+    let walker = WalkDir::new(source_dir)
+        .into_iter()
+        .filter_entry(|entry| !is_excluded(entry).unwrap_or(false));
+
+    for entry in walker {
+        let entry = entry?;
+        let src_path = entry.path();
+        let rel_path = src_path.strip_prefix(source_dir)?;
+
         let dst_path = dest_dir.join(rel_path);
 
         if entry.file_type().is_dir() {
@@ -240,18 +350,156 @@ fn copy_files(
     Ok(())
 }
 
-fn has_glob_metacharacters(s: &str) -> bool {
-    s.contains(|c| matches!(c, '*' | '?' | '[' | ']'))
+/// A single node of the iterative DFS walk in [`validate_imports`]: the
+/// file being visited and the local imports still left to follow.
+struct ImportFrame {
+    path: PathBuf,
+    imports: std::vec::IntoIter<PathBuf>,
+}
+
+/// Parses `#import "..."`/`#include "..."` statements out of `path`,
+/// resolving each target relative to `path`'s parent directory - except
+/// a root-relative target (a leading `/`, Typst's package-root syntax),
+/// which resolves against `root` instead. Package imports
+/// (`@preview/...`, `@local/...`) aren't local files and are skipped.
+fn parse_local_imports(path: &Path, root: &Path) -> Result<Vec<PathBuf>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let re = Regex::new(r#"#(?:import|include)\s+"([^"]+)""#)?;
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+
+    Ok(re
+        .captures_iter(&content)
+        .filter_map(|caps| {
+            let target = caps.get(1).unwrap().as_str();
+            let target_path = target.split(':').next().unwrap_or(target);
+            if target_path.starts_with('@') {
+                return None;
+            }
+            Some(match target_path.strip_prefix('/') {
+                Some(root_relative) => root.join(root_relative),
+                None => parent.join(target_path),
+            })
+        })
+        .collect())
+}
+
+/// Walks the local `#import`/`#include` graph starting at `entry_path`,
+/// failing fast when an imported file is missing, excluded from the
+/// built package, or when the graph contains a cycle.
+///
+/// Uses an explicit work stack (rather than recursion) plus a set of
+/// the current DFS ancestors: a path already on that ancestor set when
+/// it's about to be pushed again means its importer reaches back into
+/// its own chain, i.e. a circular import. `file_matcher`/`ignore_patterns`
+/// are the same filters `copy_files` applies, so an import that resolves
+/// to a real file the package build would nonetheless drop is caught
+/// here instead of shipping as a dangling `#import`.
+fn validate_imports(
+    entry_path: &Path,
+    root: &Path,
+    file_matcher: &FileMatcher,
+    ignore_patterns: &PatternSet,
+) -> Result<()> {
+    if !entry_path.is_file() {
+        return Err(anyhow!(
+            "Entrypoint '{}' does not exist",
+            entry_path.display()
+        ));
+    }
+
+    let entry = entry_path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", entry_path.display()))?;
+    let canon_root = root
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", root.display()))?;
+
+    let mut ancestors: Vec<PathBuf> = vec![entry.clone()];
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    visited.insert(entry.clone());
+    let mut stack: Vec<ImportFrame> = vec![ImportFrame {
+        path: entry.clone(),
+        imports: parse_local_imports(&entry, root)?.into_iter(),
+    }];
+
+    while let Some(frame) = stack.last_mut() {
+        match frame.imports.next() {
+            Some(import_path) => {
+                if !import_path.is_file() {
+                    return Err(anyhow!(
+                        "Imported file '{}' referenced from '{}' does not exist",
+                        import_path.display(),
+                        frame.path.display()
+                    ));
+                }
+
+                let canon_import = import_path
+                    .canonicalize()
+                    .with_context(|| format!("Failed to resolve {}", import_path.display()))?;
+
+                if ancestors.contains(&canon_import) {
+                    return Err(anyhow!(
+                        "Circular import detected between '{}' and '{}'",
+                        frame.path.display(),
+                        canon_import.display()
+                    ));
+                }
+
+                if let Ok(rel) = canon_import.strip_prefix(&canon_root) {
+                    let rel_unix = rel.to_string_lossy().replace(std::path::MAIN_SEPARATOR, "/");
+                    let will_be_copied = file_matcher.matches(&rel_unix)
+                        && (ignore_patterns.is_empty() || !ignore_patterns.is_excluded(&rel_unix));
+                    if !will_be_copied {
+                        return Err(anyhow!(
+                            "Imported file '{}' referenced from '{}' is excluded from the built package by include/exclude or .typstignore",
+                            rel.display(),
+                            frame.path.display()
+                        ));
+                    }
+                }
+
+                if visited.insert(canon_import.clone()) {
+                    ancestors.push(canon_import.clone());
+                    let imports = parse_local_imports(&canon_import, root)?;
+                    stack.push(ImportFrame {
+                        path: canon_import,
+                        imports: imports.into_iter(),
+                    });
+                }
+            }
+            None => {
+                ancestors.pop();
+                stack.pop();
+            }
+        }
+    }
+
+    Ok(())
 }
 
 fn main() -> Result<()> {
     let args = Cli::parse();
 
+    match args.command {
+        Commands::Build {
+            toml_file,
+            output_dir,
+        } => run_build(toml_file, &output_dir),
+        Commands::Init {
+            name,
+            version,
+            template,
+        } => run_init(name, &version, template),
+    }
+}
+
+fn run_build(toml_file: PathBuf, output_dir: &str) -> Result<()> {
     // Resolve typst.toml path
-    let toml_path = if args.toml_file.is_file() {
-        args.toml_file
-    } else if args.toml_file.is_dir() {
-        let path = args.toml_file.join("typst.toml");
+    let toml_path = if toml_file.is_file() {
+        toml_file
+    } else if toml_file.is_dir() {
+        let path = toml_file.join("typst.toml");
         if !path.exists() {
             return Err(anyhow!("No typst.toml found in directory"));
         }
@@ -275,25 +523,57 @@ fn main() -> Result<()> {
             compile_template(toml_dir, &config.package.name, path, entrypoint)?;
 
             if let Some(thumbnail) = &template.thumbnail {
-                generate_thumbnail(toml_dir, &config.package.name, path, entrypoint, thumbnail)?;
+                generate_thumbnail(
+                    toml_dir,
+                    &config.package.name,
+                    path,
+                    entrypoint,
+                    thumbnail,
+                    template.thumbnail_ppi,
+                    template.thumbnail_format.as_deref(),
+                )?;
             }
         }
     }
 
     // Prepare output directory
-    let output_base = Path::new(&args.output_dir);
+    let output_base = Path::new(output_dir);
     let output_dir = output_base
         .join(&config.package.name)
         .join(&config.package.version);
 
+    let entrypoint = config.package.entrypoint.as_deref().unwrap_or("main.typ");
+
+    // Load the optional .typstignore file next to typst.toml
+    let ignore_patterns = PatternSet::load(&toml_dir.join(".typstignore"))?;
+
+    let file_matcher = FileMatcher::new(
+        toml_dir,
+        &config.package.include.unwrap_or_default(),
+        &config.package.exclude.unwrap_or_default(),
+        &["typst.toml", entrypoint],
+    )?;
+
+    // Validate the local import graph before copying so broken packages
+    // (missing or circular imports, or imports the include/exclude or
+    // .typstignore rules would silently drop) fail fast.
+    validate_imports(
+        &toml_dir.join(entrypoint),
+        toml_dir,
+        &file_matcher,
+        &ignore_patterns,
+    )
+    .context("Import validation failed")?;
+
     // Copy files
     copy_files(
         toml_dir,
         &output_dir,
-        &config.package.exclude.unwrap_or_default(),
+        &file_matcher,
+        &ignore_patterns,
         &config.package.name,
         &config.package.version,
-        &config.package.entrypoint.as_deref().unwrap_or("main.typ"),
+        entrypoint,
     )?;
 
     println!(
@@ -305,3 +585,216 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Scaffolds a new Typst package directory named `name`, with a starter
+/// `typst.toml`, `main.typ`, and (when `with_template` is set) a
+/// `template/` skeleton plus the manifest's `[template]` section.
+///
+/// `name` is required but may be omitted on the command line, in which
+/// case it - along with `version` and `with_template` - is prompted
+/// for interactively, so `typst-build init` works as a guided flow as
+/// well as a scriptable one.
+fn run_init(name: Option<String>, version: &str, with_template: bool) -> Result<()> {
+    let interactive = name.is_none();
+
+    let name = match name {
+        Some(name) => name,
+        None => prompt_line("Package name", None)?,
+    };
+
+    let package_dir = Path::new(&name);
+    if package_dir.exists() {
+        return Err(anyhow!("Directory '{}' already exists", name));
+    }
+
+    validate_init_name(&name)?;
+
+    let version = if interactive {
+        prompt_line("Version", Some(version))?
+    } else {
+        version.to_string()
+    };
+
+    let with_template = if interactive {
+        prompt_bool("Include a template?", with_template)?
+    } else {
+        with_template
+    };
+
+    fs::create_dir_all(package_dir)?;
+
+    let template_section = if with_template {
+        "\n[template]\npath = \"template\"\nentrypoint = \"main.typ\"\nthumbnail = \"thumbnail.png\"\n"
+    } else {
+        ""
+    };
+
+    fs::write(
+        package_dir.join("typst.toml"),
+        format!(
+            "[package]\nname = \"{name}\"\nversion = \"{version}\"\nentrypoint = \"main.typ\"\n{template_section}"
+        ),
+    )?;
+
+    fs::write(
+        package_dir.join("main.typ"),
+        format!("// Welcome to {name}\n"),
+    )?;
+
+    if with_template {
+        let template_dir = package_dir.join("template");
+        fs::create_dir_all(&template_dir)?;
+        // A relative import into the entrypoint, not the `@preview/...`
+        // form: `compile_template` compiles this file before `copy_files`
+        // has had a chance to rewrite it, and the package hasn't been
+        // published yet for `@preview/{name}:{version}` to resolve.
+        fs::write(template_dir.join("main.typ"), "#import \"../main.typ\": *\n")?;
+    }
+
+    println!("Created package '{}' in {}", name, package_dir.display());
+
+    Ok(())
+}
+
+/// Validates that `name` is safe to use as both a package identifier
+/// and a single path component: non-empty, starting with a letter, and
+/// containing only letters, digits, `-`, or `_`.
+fn validate_init_name(name: &str) -> Result<()> {
+    let starts_with_letter = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic());
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if !starts_with_letter || !valid_chars {
+        return Err(anyhow!(
+            "Package name '{}' must start with a letter and contain only letters, digits, '-' or '_'",
+            name
+        ));
+    }
+
+    Ok(())
+}
+
+/// Prompts on stdout for a line of input, returning `default` unchanged
+/// when the user enters nothing. With no `default`, re-prompts until a
+/// non-empty line is entered.
+fn prompt_line(label: &str, default: Option<&str>) -> Result<String> {
+    loop {
+        match default {
+            Some(default) => print!("{} [{}]: ", label, default),
+            None => print!("{}: ", label),
+        }
+        io::stdout().flush().ok();
+
+        let mut input = String::new();
+        let bytes_read = io::stdin().read_line(&mut input)?;
+        if bytes_read == 0 {
+            return Err(anyhow!("No input received for '{}'", label));
+        }
+        let input = input.trim();
+
+        if !input.is_empty() {
+            return Ok(input.to_string());
+        }
+        if let Some(default) = default {
+            return Ok(default.to_string());
+        }
+    }
+}
+
+/// Prompts on stdout for a yes/no answer, returning `default` when the
+/// user enters nothing or anything other than a recognized yes/no.
+fn prompt_bool(label: &str, default: bool) -> Result<bool> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    print!("{} [{}]: ", label, hint);
+    io::stdout().flush().ok();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(match input.trim().to_lowercase().as_str() {
+        "" => default,
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("typst_build_test_{}_{}", std::process::id(), name))
+    }
+
+    /// A `FileMatcher`/`PatternSet` pair that excludes nothing, for tests
+    /// that only care about import-graph validation.
+    fn allow_all() -> (FileMatcher, PatternSet) {
+        (
+            FileMatcher::new(Path::new("."), &[], &[], &[]).unwrap(),
+            PatternSet::default(),
+        )
+    }
+
+    #[test]
+    fn validate_imports_detects_missing_file() {
+        let dir = temp_path("missing_import");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("main.typ"), "#import \"missing.typ\": *\n").unwrap();
+
+        let (file_matcher, ignore_patterns) = allow_all();
+        let err = validate_imports(&dir.join("main.typ"), &dir, &file_matcher, &ignore_patterns)
+            .unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(err.to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn validate_imports_detects_cycles() {
+        let dir = temp_path("cyclic_import");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.typ"), "#import \"b.typ\": *\n").unwrap();
+        fs::write(dir.join("b.typ"), "#import \"a.typ\": *\n").unwrap();
+
+        let (file_matcher, ignore_patterns) = allow_all();
+        let err = validate_imports(&dir.join("a.typ"), &dir, &file_matcher, &ignore_patterns)
+            .unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(err.to_string().contains("Circular import"));
+    }
+
+    #[test]
+    fn validate_imports_resolves_root_relative_paths() {
+        let dir = temp_path("root_relative_import");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/page.typ"), "#import \"/utils.typ\": *\n").unwrap();
+        fs::write(dir.join("utils.typ"), "").unwrap();
+
+        let (file_matcher, ignore_patterns) = allow_all();
+        let result =
+            validate_imports(&dir.join("sub/page.typ"), &dir, &file_matcher, &ignore_patterns);
+        fs::remove_dir_all(&dir).unwrap();
+
+        result.unwrap();
+    }
+
+    #[test]
+    fn validate_imports_detects_excluded_import() {
+        let dir = temp_path("excluded_import");
+        fs::create_dir_all(dir.join("lib")).unwrap();
+        fs::write(dir.join("main.typ"), "#import \"lib/helper.typ\": *\n").unwrap();
+        fs::write(dir.join("lib/helper.typ"), "").unwrap();
+
+        let file_matcher =
+            FileMatcher::new(&dir, &[], &["lib/**".to_string()], &["main.typ"]).unwrap();
+        let ignore_patterns = PatternSet::default();
+        let err = validate_imports(&dir.join("main.typ"), &dir, &file_matcher, &ignore_patterns)
+            .unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(err.to_string().contains("excluded from the built package"));
+    }
+}